@@ -1,6 +1,14 @@
-use std::{cmp::Ordering, fmt, sync::Arc};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt,
+    sync::{Arc, RwLock},
+};
 
 use nanbox::{NanBox, NanBoxable};
+use num_complex::Complex64;
+use num_rational::Rational64;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::array2::Array;
 
@@ -10,13 +18,31 @@ fn _value_is_small() {
     let _: u64 = unsafe { std::mem::transmute(Value::from(0.0)) };
 }
 
+// `PartialRef`/`ArrayRef` point at the data of an `Arc<Partial>`/`Arc<Array>`
+// (via `Arc::into_raw`), not a lone `Box`, so that `Value::clone` can share
+// the allocation instead of deep-copying it.
 type PartialRef = *mut Partial;
 type ArrayRef = *mut Array;
+type ComplexRef = *mut Complex64;
+type RationalRef = *mut Rational64;
+// `BoxRef` points at the data of an `Arc<RwLock<Value>>`, shared (not
+// copy-on-write) on `Clone` — a box is a mutable *shared* cell, so every
+// clone must keep seeing writes made through any other clone.
+type BoxRef = *mut RwLock<Value>;
+// `dyn NativeObject` is a fat pointer, too wide for a NaN-box payload, so the
+// box stores a thin pointer to a heap-allocated `Arc<dyn NativeObject>`
+// rather than the trait object directly — one extra indirection, same
+// Arc-sharing `Clone`/`Drop` as every other boxed variant.
+type NativeRef = *mut Arc<dyn NativeObject>;
 const NUM_TAG: u8 = 0;
 const CHAR_TAG: u8 = 1;
 const FUNCTION_TAG: u8 = 2;
 const PARTIAL_TAG: u8 = 3;
 const ARRAY_TAG: u8 = 4;
+const COMPLEX_TAG: u8 = 5;
+const RATIONAL_TAG: u8 = 6;
+const BOX_TAG: u8 = 7;
+const NATIVE_TAG: u8 = 8;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum RawType {
@@ -25,15 +51,23 @@ pub enum RawType {
     Function,
     Partial,
     Array,
+    Complex,
+    Rational,
+    Box,
+    Native,
 }
 
-static RAW_TYPES: [RawType; 5] = {
-    let mut types = [RawType::Number; 5];
+static RAW_TYPES: [RawType; 9] = {
+    let mut types = [RawType::Number; 9];
     types[NUM_TAG as usize] = RawType::Number;
     types[CHAR_TAG as usize] = RawType::Char;
     types[FUNCTION_TAG as usize] = RawType::Function;
     types[PARTIAL_TAG as usize] = RawType::Partial;
     types[ARRAY_TAG as usize] = RawType::Array;
+    types[COMPLEX_TAG as usize] = RawType::Complex;
+    types[RATIONAL_TAG as usize] = RawType::Rational;
+    types[BOX_TAG as usize] = RawType::Box;
+    types[NATIVE_TAG as usize] = RawType::Native;
     types
 };
 
@@ -56,6 +90,47 @@ impl Value {
     pub fn is_array(&self) -> bool {
         self.0.tag() == ARRAY_TAG as u32
     }
+    pub fn is_complex(&self) -> bool {
+        self.0.tag() == COMPLEX_TAG as u32
+    }
+    pub fn is_rational(&self) -> bool {
+        self.0.tag() == RATIONAL_TAG as u32
+    }
+    pub fn is_box(&self) -> bool {
+        self.0.tag() == BOX_TAG as u32
+    }
+    /// Wraps `v` in a mutable shared cell: clones of the returned `Value`
+    /// all alias the same cell, so a write through one is visible through
+    /// the others.
+    pub fn new_box(v: Value) -> Self {
+        Self(unsafe {
+            NanBox::new::<BoxRef>(BOX_TAG, Arc::into_raw(Arc::new(RwLock::new(v))) as BoxRef)
+        })
+    }
+    /// Reads the box's current contents out by cloning them.
+    pub fn box_get(&self) -> Value {
+        assert!(self.is_box());
+        unsafe { (*self.0.unpack::<BoxRef>()).read().unwrap().clone() }
+    }
+    /// Replaces the box's contents, visible through every alias of this cell.
+    pub fn box_set(&self, v: Value) {
+        assert!(self.is_box());
+        unsafe { *(*self.0.unpack::<BoxRef>()).write().unwrap() = v }
+    }
+    pub fn is_native(&self) -> bool {
+        self.0.tag() == NATIVE_TAG as u32
+    }
+    /// Wraps a host resource (file handle, socket, FFI object, ...) as an
+    /// opaque value the embedding application can register without touching
+    /// this enum.
+    pub fn new_native<T: NativeObject + 'static>(obj: T) -> Self {
+        let arc: Arc<dyn NativeObject> = Arc::new(obj);
+        Self(unsafe { NanBox::new::<NativeRef>(NATIVE_TAG, Box::into_raw(Box::new(arc))) })
+    }
+    pub fn native(&self) -> &dyn NativeObject {
+        assert!(self.is_native());
+        unsafe { (*self.0.unpack::<NativeRef>()).as_ref() }
+    }
     pub fn number(&self) -> f64 {
         assert!(self.is_num());
         unsafe { self.0.unpack::<f64>() }
@@ -74,7 +149,14 @@ impl Value {
     }
     pub fn partial_mut(&mut self) -> &mut Partial {
         assert!(self.is_partial());
-        unsafe { &mut *self.0.unpack::<PartialRef>() }
+        unsafe {
+            let ptr = self.0.unpack::<PartialRef>();
+            let unique = Self::make_unique(ptr);
+            if unique != ptr {
+                self.0 = NanBox::new::<PartialRef>(PARTIAL_TAG, unique);
+            }
+            &mut *unique
+        }
     }
     pub fn array(&self) -> &Array {
         assert!(self.is_array());
@@ -82,11 +164,64 @@ impl Value {
     }
     pub fn array_mut(&mut self) -> &mut Array {
         assert!(self.is_array());
-        unsafe { &mut *self.0.unpack::<ArrayRef>() }
+        unsafe {
+            let ptr = self.0.unpack::<ArrayRef>();
+            let unique = Self::make_unique(ptr);
+            if unique != ptr {
+                self.0 = NanBox::new::<ArrayRef>(ARRAY_TAG, unique);
+            }
+            &mut *unique
+        }
+    }
+    pub fn complex(&self) -> &Complex64 {
+        assert!(self.is_complex());
+        unsafe { &*self.0.unpack::<ComplexRef>() }
+    }
+    pub fn rational(&self) -> &Rational64 {
+        assert!(self.is_rational());
+        unsafe { &*self.0.unpack::<RationalRef>() }
+    }
+    /// Ensures the `Arc` backing `ptr` is uniquely owned, cloning its
+    /// contents if other `Value`s are sharing it, and returns a pointer to
+    /// the (possibly new) sole owner's data. This is the copy-on-write half
+    /// of the shared-immutable model: reads are free sharing, writes pay for
+    /// a clone only when aliased.
+    unsafe fn make_unique<T: Clone>(ptr: *mut T) -> *mut T {
+        let mut arc = Arc::from_raw(ptr as *const T);
+        let unique = Arc::make_mut(&mut arc) as *mut T;
+        std::mem::forget(arc);
+        unique
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A host-registered object an embedding application can put into a `Value`
+/// without extending this enum: an open file, a socket, an FFI handle, or
+/// any other native resource.
+pub trait NativeObject: fmt::Debug + Send + Sync {
+    /// A human-readable name, used in the error messages of the default
+    /// `call` and of `Value`'s `Serialize` impl.
+    fn type_name(&self) -> &'static str;
+    /// Lets a native object stand in for a `Function` when the interpreter
+    /// calls whatever value is in that slot.
+    fn call(&self, args: &[Value]) -> Result<Value, String> {
+        let _ = args;
+        Err(format!("{} is not callable", self.type_name()))
+    }
+    /// Defaults to pointer identity; override for value-based equality.
+    fn native_eq(&self, other: &dyn NativeObject) -> bool {
+        std::ptr::eq(
+            self as *const Self as *const (),
+            other as *const dyn NativeObject as *const (),
+        )
+    }
+    /// Defaults to an arbitrary (but stable) pointer-identity order;
+    /// override for a meaningful one.
+    fn native_cmp(&self, other: &dyn NativeObject) -> Ordering {
+        (self as *const Self as *const ()).cmp(&(other as *const dyn NativeObject as *const ()))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Function {
     pub(crate) start: u32,
     pub(crate) params: u16,
@@ -171,10 +306,30 @@ impl Drop for Value {
     fn drop(&mut self) {
         match self.raw_ty() {
             RawType::Partial => unsafe {
-                drop(Box::from_raw(self.0.unpack::<PartialRef>()));
+                drop(Arc::from_raw(
+                    self.0.unpack::<PartialRef>() as *const Partial
+                ));
             },
             RawType::Array => unsafe {
-                drop(Box::from_raw(self.0.unpack::<ArrayRef>()));
+                drop(Arc::from_raw(self.0.unpack::<ArrayRef>() as *const Array));
+            },
+            RawType::Complex => unsafe {
+                drop(Arc::from_raw(
+                    self.0.unpack::<ComplexRef>() as *const Complex64
+                ));
+            },
+            RawType::Rational => unsafe {
+                drop(Arc::from_raw(
+                    self.0.unpack::<RationalRef>() as *const Rational64
+                ));
+            },
+            RawType::Box => unsafe {
+                drop(Arc::from_raw(
+                    self.0.unpack::<BoxRef>() as *const RwLock<Value>
+                ));
+            },
+            RawType::Native => unsafe {
+                drop(Box::from_raw(self.0.unpack::<NativeRef>()));
             },
             _ => {}
         }
@@ -184,20 +339,129 @@ impl Drop for Value {
 impl Clone for Value {
     fn clone(&self) -> Self {
         match self.raw_ty() {
+            // Sharing the `Arc` instead of deep-copying the payload is what
+            // makes stack-shuffling (`dup`, reductions, cell iteration)
+            // cheap regardless of how much data the value holds.
             RawType::Partial => Self(unsafe {
-                NanBox::new::<PartialRef>(
-                    PARTIAL_TAG,
-                    Box::into_raw(Box::new(self.partial().clone())),
-                )
+                let ptr = self.0.unpack::<PartialRef>();
+                Arc::increment_strong_count(ptr as *const Partial);
+                NanBox::new::<PartialRef>(PARTIAL_TAG, ptr)
             }),
             RawType::Array => Self(unsafe {
-                NanBox::new::<ArrayRef>(ARRAY_TAG, Box::into_raw(Box::new(self.array().clone())))
+                let ptr = self.0.unpack::<ArrayRef>();
+                Arc::increment_strong_count(ptr as *const Array);
+                NanBox::new::<ArrayRef>(ARRAY_TAG, ptr)
+            }),
+            RawType::Complex => Self(unsafe {
+                let ptr = self.0.unpack::<ComplexRef>();
+                Arc::increment_strong_count(ptr as *const Complex64);
+                NanBox::new::<ComplexRef>(COMPLEX_TAG, ptr)
+            }),
+            RawType::Rational => Self(unsafe {
+                let ptr = self.0.unpack::<RationalRef>();
+                Arc::increment_strong_count(ptr as *const Rational64);
+                NanBox::new::<RationalRef>(RATIONAL_TAG, ptr)
+            }),
+            // Boxes are shared, not copy-on-write: every clone must alias
+            // the same cell so writes through one are visible via the rest.
+            RawType::Box => Self(unsafe {
+                let ptr = self.0.unpack::<BoxRef>();
+                Arc::increment_strong_count(ptr as *const RwLock<Value>);
+                NanBox::new::<BoxRef>(BOX_TAG, ptr)
+            }),
+            // The `Arc<dyn NativeObject>` is shared; only its thin-pointer
+            // `Box` wrapper is duplicated so each `Value` owns its own slot.
+            RawType::Native => Self(unsafe {
+                let cloned: Arc<dyn NativeObject> = (*self.0.unpack::<NativeRef>()).clone();
+                NanBox::new::<NativeRef>(NATIVE_TAG, Box::into_raw(Box::new(cloned)))
             }),
             _ => Self(self.0),
         }
     }
 }
 
+// Guards `Value` comparison against boxes that (transitively) contain
+// themselves, e.g. `b1.box_set(b2.clone()); b2.box_set(b1.clone())`. A plain
+// `a == b` pointer check only catches the direct `b1 == b1` case; a mutual
+// cycle like the one above compares `b1` against `b2` (different pointers),
+// recurses into their contents, and lands back on the same `(b1, b2)` pair
+// while the outer `RwLock` read guards are still held — `std::sync::RwLock`
+// doesn't guarantee a thread can re-acquire a read lock it already holds, so
+// that recursion can deadlock as well as blow the stack. We instead track
+// the pointer pairs currently being compared on this thread and treat a pair
+// already on the path as equal, which breaks the cycle without re-locking.
+thread_local! {
+    static BOX_CMP_PATH: RefCell<Vec<(*const (), *const ())>> = RefCell::new(Vec::new());
+}
+
+/// Pops `BOX_CMP_PATH`'s top entry on drop, including on unwind, so a panic
+/// partway through a comparison can't leave a stale pair on the path.
+struct BoxCmpGuard;
+
+impl Drop for BoxCmpGuard {
+    fn drop(&mut self) {
+        BOX_CMP_PATH.with(|path| {
+            path.borrow_mut().pop();
+        });
+    }
+}
+
+fn on_box_cmp_path(pair: (*const (), *const ())) -> bool {
+    BOX_CMP_PATH.with(|path| {
+        path.borrow()
+            .iter()
+            .any(|&(a, b)| (a, b) == pair || (a, b) == (pair.1, pair.0))
+    })
+}
+
+fn box_eq(a: BoxRef, b: BoxRef) -> bool {
+    let pair = (a as *const (), b as *const ());
+    if pair.0 == pair.1 || on_box_cmp_path(pair) {
+        return true;
+    }
+    BOX_CMP_PATH.with(|path| path.borrow_mut().push(pair));
+    let _guard = BoxCmpGuard;
+    unsafe { *(*a).read().unwrap() == *(*b).read().unwrap() }
+}
+
+fn box_cmp(a: BoxRef, b: BoxRef) -> Ordering {
+    let pair = (a as *const (), b as *const ());
+    if pair.0 == pair.1 || on_box_cmp_path(pair) {
+        return Ordering::Equal;
+    }
+    BOX_CMP_PATH.with(|path| path.borrow_mut().push(pair));
+    let _guard = BoxCmpGuard;
+    unsafe { (*a).read().unwrap().cmp(&(*b).read().unwrap()) }
+}
+
+// Same cycle problem as `box_eq`/`box_cmp`, but for `Debug`: a box that
+// (transitively) contains itself would otherwise have its `Debug` impl
+// recurse into its own contents forever and blow the stack, since
+// `self.box_get()` just reads through to the aliased `Value` again.
+thread_local! {
+    static BOX_FMT_PATH: RefCell<Vec<*const ()>> = RefCell::new(Vec::new());
+}
+
+struct BoxFmtGuard;
+
+impl Drop for BoxFmtGuard {
+    fn drop(&mut self) {
+        BOX_FMT_PATH.with(|path| {
+            path.borrow_mut().pop();
+        });
+    }
+}
+
+fn fmt_box(ptr: BoxRef, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let key = ptr as *const ();
+    if BOX_FMT_PATH.with(|path| path.borrow().contains(&key)) {
+        return write!(f, "box(<cycle>)");
+    }
+    BOX_FMT_PATH.with(|path| path.borrow_mut().push(key));
+    let _guard = BoxFmtGuard;
+    write!(f, "box({:?})", unsafe { &*(*ptr).read().unwrap() })
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match (self.raw_ty(), other.raw_ty()) {
@@ -210,6 +474,24 @@ impl PartialEq for Value {
             (RawType::Function, RawType::Function) => self.function() == other.function(),
             (RawType::Partial, RawType::Partial) => self.partial() == other.partial(),
             (RawType::Array, RawType::Array) => self.array() == other.array(),
+            // Matches `Ord`'s NaN-as-equal rule via `total_cmp_f64` (and
+            // `Number`'s own `eq` above) rather than `Complex64`'s derived
+            // field-wise `PartialEq`, which treats NaN as unequal to itself
+            // and would otherwise make `eq`/`cmp` disagree for NaN components.
+            (RawType::Complex, RawType::Complex) => {
+                let (a, b) = (self.complex(), other.complex());
+                total_cmp_f64(a.re, b.re).is_eq() && total_cmp_f64(a.im, b.im).is_eq()
+            }
+            (RawType::Rational, RawType::Rational) => self.rational() == other.rational(),
+            (RawType::Box, RawType::Box) => {
+                let (a, b) = unsafe { (self.0.unpack::<BoxRef>(), other.0.unpack::<BoxRef>()) };
+                box_eq(a, b)
+            }
+            (RawType::Native, RawType::Native) => self.native().native_eq(other.native()),
+            // No promotion: `Number(2.0)` and `Rational(2/1)` compare unequal
+            // even though they denote the same value, same as any other pair
+            // of differing `RawType`s. Add promotion rules here if Uiua ever
+            // needs numeric-tower equality across these variants.
             _ => false,
         }
     }
@@ -226,21 +508,34 @@ impl PartialOrd for Value {
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self.raw_ty(), other.raw_ty()) {
-            (RawType::Number, RawType::Number) => {
-                let a = self.number();
-                let b = other.number();
-                a.partial_cmp(&b)
-                    .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
-            }
+            (RawType::Number, RawType::Number) => total_cmp_f64(self.number(), other.number()),
             (RawType::Char, RawType::Char) => self.char().cmp(&other.char()),
             (RawType::Function, RawType::Function) => self.function().cmp(&other.function()),
             (RawType::Partial, RawType::Partial) => self.partial().cmp(other.partial()),
             (RawType::Array, RawType::Array) => self.array().cmp(other.array()),
+            (RawType::Complex, RawType::Complex) => {
+                let (a, b) = (self.complex(), other.complex());
+                total_cmp_f64(a.re, b.re).then_with(|| total_cmp_f64(a.im, b.im))
+            }
+            (RawType::Rational, RawType::Rational) => self.rational().cmp(other.rational()),
+            (RawType::Box, RawType::Box) => {
+                let (a, b) = unsafe { (self.0.unpack::<BoxRef>(), other.0.unpack::<BoxRef>()) };
+                box_cmp(a, b)
+            }
+            (RawType::Native, RawType::Native) => self.native().native_cmp(other.native()),
             (a, b) => a.cmp(&b),
         }
     }
 }
 
+/// Total order over `f64` that breaks the `NaN`-is-unordered rule the same
+/// way `Number`'s `Value::eq` does: all `NaN`s compare equal to each other
+/// and greater than every other value.
+fn total_cmp_f64(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
 impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.raw_ty() {
@@ -249,6 +544,10 @@ impl fmt::Debug for Value {
             RawType::Function => write!(f, "{:?}", self.function()),
             RawType::Partial => write!(f, "{:?}", self.partial()),
             RawType::Array => write!(f, "{:?}", self.array()),
+            RawType::Complex => write!(f, "{:?}", self.complex()),
+            RawType::Rational => write!(f, "{:?}", self.rational()),
+            RawType::Box => fmt_box(unsafe { self.0.unpack::<BoxRef>() }, f),
+            RawType::Native => write!(f, "{:?}", self.native()),
         }
     }
 }
@@ -273,12 +572,246 @@ impl From<Function> for Value {
 
 impl From<Partial> for Value {
     fn from(p: Partial) -> Self {
-        Self(unsafe { NanBox::new::<PartialRef>(PARTIAL_TAG, Box::into_raw(Box::new(p))) })
+        Self(unsafe {
+            NanBox::new::<PartialRef>(PARTIAL_TAG, Arc::into_raw(Arc::new(p)) as PartialRef)
+        })
     }
 }
 
 impl From<Array> for Value {
     fn from(a: Array) -> Self {
-        Self(unsafe { NanBox::new::<ArrayRef>(ARRAY_TAG, Box::into_raw(Box::new(a))) })
+        Self(unsafe { NanBox::new::<ArrayRef>(ARRAY_TAG, Arc::into_raw(Arc::new(a)) as ArrayRef) })
+    }
+}
+
+impl From<Complex64> for Value {
+    fn from(c: Complex64) -> Self {
+        Self(unsafe {
+            NanBox::new::<ComplexRef>(COMPLEX_TAG, Arc::into_raw(Arc::new(c)) as ComplexRef)
+        })
+    }
+}
+
+impl From<Rational64> for Value {
+    fn from(r: Rational64) -> Self {
+        Self(unsafe {
+            NanBox::new::<RationalRef>(RATIONAL_TAG, Arc::into_raw(Arc::new(r)) as RationalRef)
+        })
+    }
+}
+
+/// Self-describing, externally-tagged stand-in for `Value` used only for
+/// `serde`. `Value`'s real representation is a NaN-boxed `u64`, which isn't
+/// meaningful outside this process, so we (de)serialize through this enum
+/// instead and reconstruct the heap-boxed variants via the `From` impls
+/// above. Unknown tags are rejected by serde's own enum deserialization,
+/// so there's no unsafe decoding to get wrong.
+#[derive(Serialize, Deserialize)]
+enum ValueRepr {
+    Number(f64),
+    Char(char),
+    Function(Function),
+    Partial(PartialRepr),
+    Array(Array),
+    Complex(Complex64),
+    Rational(Rational64),
+    // Serializes a box by its current contents. Round-tripping loses both
+    // aliasing (every deserialized box is its own fresh cell) and any
+    // self-reference (serializing a box that transitively contains itself
+    // recurses forever: unlike `Value`'s `Debug` impl, there is no cycle
+    // guard here).
+    Box(Box<Value>),
+}
+
+/// `Partial::args` is an `Arc<[Value]>`; we (de)serialize it as a plain
+/// `Vec<Value>` and convert, rather than requiring serde's `rc` feature.
+#[derive(Serialize, Deserialize)]
+struct PartialRepr {
+    function: Function,
+    args: Vec<Value>,
+}
+
+impl From<&Partial> for PartialRepr {
+    fn from(p: &Partial) -> Self {
+        Self {
+            function: p.function,
+            args: p.args.to_vec(),
+        }
+    }
+}
+
+impl From<PartialRepr> for Partial {
+    fn from(r: PartialRepr) -> Self {
+        Self {
+            function: r.function,
+            args: Arc::from(r.args),
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.raw_ty() {
+            RawType::Number => ValueRepr::Number(self.number()).serialize(serializer),
+            RawType::Char => ValueRepr::Char(self.char()).serialize(serializer),
+            RawType::Function => ValueRepr::Function(self.function()).serialize(serializer),
+            RawType::Partial => ValueRepr::Partial(self.partial().into()).serialize(serializer),
+            RawType::Array => ValueRepr::Array(self.array().clone()).serialize(serializer),
+            RawType::Complex => ValueRepr::Complex(*self.complex()).serialize(serializer),
+            RawType::Rational => ValueRepr::Rational(*self.rational()).serialize(serializer),
+            RawType::Box => ValueRepr::Box(Box::new(self.box_get())).serialize(serializer),
+            // Native values are opaque host resources with no general
+            // on-disk representation, so (unlike every other variant) this
+            // is a hard error rather than a lossy fallback.
+            RawType::Native => Err(serde::ser::Error::custom(format!(
+                "cannot serialize native value of type `{}`",
+                self.native().type_name()
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match ValueRepr::deserialize(deserializer)? {
+            ValueRepr::Number(n) => Value::from(n),
+            ValueRepr::Char(c) => Value::from(c),
+            ValueRepr::Function(f) => Value::from(f),
+            ValueRepr::Partial(p) => Value::from(Partial::from(p)),
+            ValueRepr::Array(a) => Value::from(a),
+            ValueRepr::Complex(c) => Value::from(c),
+            ValueRepr::Rational(r) => Value::from(r),
+            ValueRepr::Box(v) => Value::new_box(*v),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `array2::Array` isn't available to this crate's test harness, but
+    // `Partial` shares the exact same `Arc`/`make_unique` machinery, so this
+    // exercises the same copy-on-write path `array_mut` would.
+    #[test]
+    fn partial_mut_copies_on_write_instead_of_mutating_shared_clones() {
+        let mut original = Value::from(Partial {
+            function: Function::nil(),
+            args: Arc::from(vec![Value::from(1.0)]),
+        });
+        let shared = original.clone();
+
+        original.partial_mut().args = Arc::from(vec![Value::from(2.0)]);
+
+        assert_eq!(shared.partial().args[0], Value::from(1.0));
+        assert_eq!(original.partial().args[0], Value::from(2.0));
+    }
+
+    #[test]
+    fn box_clones_alias_the_same_cell() {
+        let b = Value::new_box(Value::from(1.0));
+        let alias = b.clone();
+
+        alias.box_set(Value::from(2.0));
+
+        assert_eq!(b.box_get(), Value::from(2.0));
+    }
+
+    // Regression test for a deadlock/infinite-recursion bug: comparing two
+    // boxes that transitively contain each other used to recurse into
+    // `RwLock::read` on the same locks it was still holding higher up the
+    // call stack. This just needs to return instead of hanging or
+    // stack-overflowing; the particular `Equal` verdict falls out of
+    // treating an in-progress pair as equal, not out of any deeper meaning.
+    #[test]
+    fn box_mutual_cycle_does_not_deadlock() {
+        let b1 = Value::new_box(Value::from(0.0));
+        let b2 = Value::new_box(Value::from(0.0));
+        b1.box_set(b2.clone());
+        b2.box_set(b1.clone());
+
+        assert_eq!(b1, b2);
+        assert_eq!(b1.cmp(&b2), Ordering::Equal);
+    }
+
+    #[test]
+    fn box_mutual_cycle_does_not_overflow_the_stack_when_debug_formatted() {
+        let b1 = Value::new_box(Value::from(0.0));
+        let b2 = Value::new_box(Value::from(0.0));
+        b1.box_set(b2.clone());
+        b2.box_set(b1.clone());
+
+        let formatted = format!("{b1:?}");
+        assert!(formatted.contains("<cycle>"));
+    }
+
+    // `serde_json` can't carry this invariant: it writes non-finite floats
+    // as `null`, which then fails to deserialize back into a bare `f64`
+    // field. Go through a binary format that passes `f64` through
+    // byte-for-byte instead, to actually check that a NaN number survives
+    // the round-trip bit for bit.
+    #[test]
+    fn nan_roundtrips_bit_for_bit_through_a_binary_format() {
+        let v = Value::from(f64::NAN);
+
+        let bytes = bincode::serialize(&v).unwrap();
+        let back: Value = bincode::deserialize(&bytes).unwrap();
+
+        assert!(back.is_num());
+        assert_eq!(back.number().to_bits(), f64::NAN.to_bits());
+    }
+
+    // Regression test for `eq`/`cmp` disagreeing on NaN components: a
+    // derived `Complex64::eq` would say these are unequal (NaN != NaN),
+    // while `Ord` (via `total_cmp_f64`) would say they're `Equal`.
+    #[test]
+    fn complex_nan_eq_matches_cmp() {
+        let a = Value::from(Complex64::new(f64::NAN, 0.0));
+        let b = Value::from(Complex64::new(f64::NAN, 0.0));
+
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+    }
+
+    #[derive(Debug)]
+    struct TestNative(u32);
+
+    impl NativeObject for TestNative {
+        fn type_name(&self) -> &'static str {
+            "TestNative"
+        }
+    }
+
+    #[test]
+    fn native_clone_shares_the_underlying_object_instead_of_copying_it() {
+        let original = Value::new_native(TestNative(1));
+        let alias = original.clone();
+
+        assert!(std::ptr::eq(
+            original.native() as *const dyn NativeObject as *const (),
+            alias.native() as *const dyn NativeObject as *const (),
+        ));
+        assert_eq!(original, alias);
+        assert_eq!(original.cmp(&alias), Ordering::Equal);
+    }
+
+    #[test]
+    fn native_default_eq_and_cmp_use_pointer_identity() {
+        let a = Value::new_native(TestNative(1));
+        let b = Value::new_native(TestNative(1));
+
+        // Same field value, but distinct allocations: the default
+        // `native_eq`/`native_cmp` compare identity, not contents.
+        assert_ne!(a, b);
+        assert_ne!(a.cmp(&b), Ordering::Equal);
+    }
+
+    #[test]
+    fn native_default_call_errors_with_the_type_name() {
+        let n = Value::new_native(TestNative(1));
+        assert_eq!(
+            n.native().call(&[]).unwrap_err(),
+            "TestNative is not callable"
+        );
     }
 }